@@ -1,3 +1,6 @@
+use std::marker::PhantomData;
+
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 /// `Enable<T>` is a wrapper to properly `Serialize` and `Deserialize`
@@ -76,6 +79,38 @@ impl<T> Enable<T> {
     }
 }
 
+/// `#[serde(with = "serde_enabled")]` adapter for a plain `Option<T>` field,
+/// for when a struct already uses `Option<T>` and converting it to
+/// `Enable<T>` isn't practical. Deserializes the same `enable: true/false`
+/// flattened shape as `Enable<T>`, yielding `None` for the `Off` case and
+/// `Some(inner)` for `On`.
+pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: serde::Serializer,
+{
+    let inner = match value {
+        Some(inner) => InnerEnable::On(On {
+            enable: True,
+            inner,
+        }),
+        None => InnerEnable::Off { enable: False },
+    };
+
+    inner.serialize(serializer)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    InnerEnable::<T>::deserialize(deserializer).map(|enabled| match enabled {
+        InnerEnable::On(On { inner, .. }) => Some(inner),
+        InnerEnable::Off { .. } => None,
+    })
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug)]
 struct On<T> {
     enable: True,
@@ -86,6 +121,12 @@ struct On<T> {
 #[derive(Clone, Debug)]
 struct True;
 
+impl Default for True {
+    fn default() -> Self {
+        True
+    }
+}
+
 impl Serialize for True {
     fn serialize<S>(&self, serializer: S) -> std::prelude::v1::Result<S::Ok, S::Error>
     where
@@ -112,6 +153,12 @@ impl<'de> Deserialize<'de> for True {
 #[derive(Debug, Clone)]
 struct False;
 
+impl Default for False {
+    fn default() -> Self {
+        False
+    }
+}
+
 impl Serialize for False {
     fn serialize<S>(&self, serializer: S) -> std::prelude::v1::Result<S::Ok, S::Error>
     where
@@ -135,6 +182,434 @@ impl<'de> Deserialize<'de> for False {
     }
 }
 
+/// `EnableKeepingExtras<T>` behaves like `Enable<T>`, but while `Off` it
+/// captures any sibling keys instead of discarding them, so that loading and
+/// re-saving a config file doesn't destroy a user's commented-out settings.
+/// The extras are kept in an order-preserving map so they round-trip in the
+/// order they were read, rather than being reshuffled alphabetically.
+#[derive(Clone, Debug)]
+pub enum EnableKeepingExtras<T> {
+    On(T),
+    Off(IndexMap<String, serde_json::Value>),
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum InnerEnableKeepingExtras<T> {
+    On(On<T>),
+    Off(OffKeepingExtras),
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+struct OffKeepingExtras {
+    enable: False,
+    #[serde(flatten)]
+    extra: IndexMap<String, serde_json::Value>,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for EnableKeepingExtras<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        InnerEnableKeepingExtras::<T>::deserialize(deserializer).map(|enabled| match enabled {
+            InnerEnableKeepingExtras::On(On { inner, .. }) => EnableKeepingExtras::On(inner),
+            InnerEnableKeepingExtras::Off(OffKeepingExtras { extra, .. }) => {
+                EnableKeepingExtras::Off(extra)
+            }
+        })
+    }
+}
+
+impl<T: Serialize> Serialize for EnableKeepingExtras<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let inner = match self {
+            EnableKeepingExtras::On(inner) => InnerEnableKeepingExtras::On(On {
+                enable: True,
+                inner,
+            }),
+            EnableKeepingExtras::Off(extra) => {
+                InnerEnableKeepingExtras::Off(OffKeepingExtras {
+                    enable: False,
+                    extra: extra.clone(),
+                })
+            }
+        };
+
+        inner.serialize(serializer)
+    }
+}
+
+impl<T> EnableKeepingExtras<T> {
+    pub fn into_inner(self) -> Option<T> {
+        match self {
+            EnableKeepingExtras::On(inner) => Some(inner),
+            EnableKeepingExtras::Off(_) => None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, EnableKeepingExtras::On(_))
+    }
+
+    /// The sibling keys that were present while this setting was `Off`, so
+    /// they can be inspected or migrated instead of being silently dropped.
+    pub fn disabled_extras(&self) -> Option<&IndexMap<String, serde_json::Value>> {
+        match self {
+            EnableKeepingExtras::On(_) => None,
+            EnableKeepingExtras::Off(extra) => Some(extra),
+        }
+    }
+}
+
+/// `EnableByDefault<T>` behaves like `Enable<T>`, except an absent `enable`
+/// key is treated as `On` rather than an error, matching config ergonomics
+/// where a feature block's mere presence implies it is turned on.
+#[derive(Clone, Debug)]
+pub enum EnableByDefault<T> {
+    On(T),
+    Off,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum InnerEnableByDefault<T> {
+    On(OnByDefault<T>),
+    #[allow(dead_code)]
+    Off {
+        enable: False,
+    },
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+struct OnByDefault<T> {
+    #[serde(default)]
+    enable: True,
+    #[serde(flatten)]
+    inner: T,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for EnableByDefault<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        InnerEnableByDefault::<T>::deserialize(deserializer).map(|enabled| match enabled {
+            InnerEnableByDefault::On(OnByDefault { inner, .. }) => EnableByDefault::On(inner),
+            InnerEnableByDefault::Off { .. } => EnableByDefault::Off,
+        })
+    }
+}
+
+impl<T: Serialize> Serialize for EnableByDefault<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let inner = match self {
+            EnableByDefault::On(inner) => InnerEnableByDefault::On(OnByDefault {
+                enable: True,
+                inner,
+            }),
+            EnableByDefault::Off => InnerEnableByDefault::Off { enable: False },
+        };
+
+        inner.serialize(serializer)
+    }
+}
+
+impl<T> EnableByDefault<T> {
+    pub fn into_inner(self) -> Option<T> {
+        match self {
+            EnableByDefault::On(inner) => Some(inner),
+            EnableByDefault::Off => None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, EnableByDefault::On(_))
+    }
+}
+
+/// `DisableByDefault<T>` behaves like `Enable<T>`, except an absent `enable`
+/// key is treated as `Off` rather than an error.
+#[derive(Clone, Debug)]
+pub enum DisableByDefault<T> {
+    On(T),
+    Off,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum InnerDisableByDefault<T> {
+    On(On<T>),
+    #[allow(dead_code)]
+    Off {
+        #[serde(default)]
+        enable: False,
+    },
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for DisableByDefault<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        InnerDisableByDefault::<T>::deserialize(deserializer).map(|enabled| match enabled {
+            InnerDisableByDefault::On(On { inner, .. }) => DisableByDefault::On(inner),
+            InnerDisableByDefault::Off { .. } => DisableByDefault::Off,
+        })
+    }
+}
+
+impl<T: Serialize> Serialize for DisableByDefault<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let inner = match self {
+            DisableByDefault::On(inner) => InnerDisableByDefault::On(On {
+                enable: True,
+                inner,
+            }),
+            DisableByDefault::Off => InnerDisableByDefault::Off { enable: False },
+        };
+
+        inner.serialize(serializer)
+    }
+}
+
+impl<T> DisableByDefault<T> {
+    pub fn into_inner(self) -> Option<T> {
+        match self {
+            DisableByDefault::On(inner) => Some(inner),
+            DisableByDefault::Off => None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, DisableByDefault::On(_))
+    }
+}
+
+/// A value a tag field can hold, in the spirit of Dropbox's `.tag`-based
+/// union deserializers: either a boolean or a string token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagToken {
+    Bool(bool),
+    Str(&'static str),
+}
+
+fn token_matches(token: &TagToken, value: &serde_json::Value) -> bool {
+    match token {
+        TagToken::Bool(b) => value.as_bool() == Some(*b),
+        TagToken::Str(s) => value.as_str() == Some(*s),
+    }
+}
+
+fn token_to_value(token: &TagToken) -> serde_json::Value {
+    match token {
+        TagToken::Bool(b) => serde_json::Value::Bool(*b),
+        TagToken::Str(s) => serde_json::Value::String((*s).to_string()),
+    }
+}
+
+/// Describes a tagged `On`/`Off` representation: the name of the
+/// discriminant key and the tokens that mean "on" and "off", so schemas
+/// that don't use the hardcoded `enable: true/false` shape can still plug
+/// into this crate.
+pub trait EnableTag {
+    const KEY: &'static str;
+    const ON: TagToken;
+    const OFF: TagToken;
+}
+
+/// `TaggedEnable<T, Tag>` is like `Enable<T>`, but the discriminant key and
+/// its on/off tokens are configurable through `Tag: EnableTag` instead of
+/// being hardcoded to `enable: true/false`.
+///
+/// `Clone`/`Debug` are implemented by hand rather than derived, since `Tag`
+/// is a phantom marker and shouldn't need to implement them itself.
+pub enum TaggedEnable<T, Tag> {
+    On(T, PhantomData<Tag>),
+    Off(PhantomData<Tag>),
+}
+
+impl<T: Clone, Tag> Clone for TaggedEnable<T, Tag> {
+    fn clone(&self) -> Self {
+        match self {
+            TaggedEnable::On(inner, _) => TaggedEnable::On(inner.clone(), PhantomData),
+            TaggedEnable::Off(_) => TaggedEnable::Off(PhantomData),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug, Tag> std::fmt::Debug for TaggedEnable<T, Tag> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaggedEnable::On(inner, _) => f.debug_tuple("On").field(inner).finish(),
+            TaggedEnable::Off(_) => write!(f, "Off"),
+        }
+    }
+}
+
+impl<T, Tag> TaggedEnable<T, Tag> {
+    pub fn on(inner: T) -> Self {
+        TaggedEnable::On(inner, PhantomData)
+    }
+
+    pub fn off() -> Self {
+        TaggedEnable::Off(PhantomData)
+    }
+
+    pub fn into_inner(self) -> Option<T> {
+        match self {
+            TaggedEnable::On(inner, _) => Some(inner),
+            TaggedEnable::Off(_) => None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, TaggedEnable::On(..))
+    }
+}
+
+impl<'de, T, Tag> Deserialize<'de> for TaggedEnable<T, Tag>
+where
+    T: Deserialize<'de>,
+    Tag: EnableTag,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        let tag = value
+            .as_object_mut()
+            .ok_or_else(|| serde::de::Error::custom("expected a map"))?
+            .remove(Tag::KEY)
+            .ok_or_else(|| serde::de::Error::custom(format!("missing `{}` field", Tag::KEY)))?;
+
+        if token_matches(&Tag::ON, &tag) {
+            let inner = T::deserialize(value).map_err(serde::de::Error::custom)?;
+            Ok(TaggedEnable::On(inner, PhantomData))
+        } else if token_matches(&Tag::OFF, &tag) {
+            Ok(TaggedEnable::Off(PhantomData))
+        } else {
+            Err(serde::de::Error::custom(format!(
+                "unrecognized value for `{}`: {tag}",
+                Tag::KEY
+            )))
+        }
+    }
+}
+
+impl<T, Tag> Serialize for TaggedEnable<T, Tag>
+where
+    T: Serialize,
+    Tag: EnableTag,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut value = match self {
+            TaggedEnable::On(inner, _) => {
+                serde_json::to_value(inner).map_err(serde::ser::Error::custom)?
+            }
+            TaggedEnable::Off(_) => serde_json::Value::Object(serde_json::Map::new()),
+        };
+
+        let tag_token = match self {
+            TaggedEnable::On(..) => &Tag::ON,
+            TaggedEnable::Off(_) => &Tag::OFF,
+        };
+
+        value
+            .as_object_mut()
+            .ok_or_else(|| serde::ser::Error::custom("expected inner value to serialize to a map"))?
+            .insert(Tag::KEY.to_string(), token_to_value(tag_token));
+
+        value.serialize(serializer)
+    }
+}
+
+/// `Setting<T>` is a three-state wrapper for layered configuration, where a
+/// field needs to distinguish "explicitly set to a value", "explicitly reset
+/// to the default" and "absent, so inherit whatever the layer below says".
+///
+/// `NotSet` is produced when the field is missing, via `#[serde(default)]` on
+/// the field; `Reset` round-trips through an explicit `null`; `Set(v)`
+/// round-trips as the value itself.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Setting<T> {
+    Set(T),
+    Reset,
+    #[default]
+    NotSet,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Setting<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer).map(|value| match value {
+            Some(inner) => Setting::Set(inner),
+            None => Setting::Reset,
+        })
+    }
+}
+
+impl<T: Serialize> Serialize for Setting<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Setting::Set(inner) => inner.serialize(serializer),
+            Setting::Reset | Setting::NotSet => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<T> Setting<T> {
+    pub fn set(inner: T) -> Setting<T> {
+        Setting::Set(inner)
+    }
+
+    pub fn reset() -> Setting<T> {
+        Setting::Reset
+    }
+
+    pub fn is_set(&self) -> bool {
+        matches!(self, Setting::Set(_))
+    }
+
+    pub fn is_not_set(&self) -> bool {
+        matches!(self, Setting::NotSet)
+    }
+
+    pub fn into_inner(self) -> Option<T> {
+        match self {
+            Setting::Set(inner) => Some(inner),
+            Setting::Reset | Setting::NotSet => None,
+        }
+    }
+
+    /// Merges two layers: a `Set`/`Reset` in `self` (the higher layer) wins
+    /// over whatever `other` (the lower layer) holds; `NotSet` falls through.
+    pub fn or(self, other: Setting<T>) -> Setting<T> {
+        match self {
+            Setting::NotSet => other,
+            set_or_reset => set_or_reset,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
@@ -231,4 +706,336 @@ mod tests {
         let result = serde_yaml::to_string(&o).unwrap();
         assert_eq!(result, raw,);
     }
+
+    use crate::Setting;
+
+    #[derive(Deserialize, Serialize)]
+    struct Layer {
+        #[serde(default)]
+        level: Setting<u32>,
+    }
+
+    #[test]
+    fn absent_field_is_not_set() {
+        let raw = indoc::indoc! {r#"
+            {}
+            "#};
+
+        let l: Layer = serde_yaml::from_str(raw).unwrap();
+
+        assert!(l.level.is_not_set());
+    }
+
+    #[test]
+    fn explicit_null_is_reset() {
+        let raw = indoc::indoc! {r#"
+            level: null
+            "#};
+
+        let l: Layer = serde_yaml::from_str(raw).unwrap();
+
+        assert_eq!(l.level, Setting::Reset);
+    }
+
+    #[test]
+    fn explicit_value_is_set() {
+        let raw = indoc::indoc! {r#"
+            level: 42
+            "#};
+
+        let l: Layer = serde_yaml::from_str(raw).unwrap();
+
+        assert_eq!(l.level, Setting::Set(42));
+        assert_eq!(l.level.into_inner(), Some(42));
+    }
+
+    #[test]
+    fn or_prefers_the_higher_layer() {
+        assert_eq!(Setting::Set(1).or(Setting::Set(2)), Setting::Set(1));
+        assert_eq!(Setting::Reset.or(Setting::Set(2)), Setting::Reset);
+        assert_eq!(
+            Setting::<u32>::NotSet.or(Setting::Set(2)),
+            Setting::Set(2)
+        );
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct OutsideOption {
+        #[serde(with = "crate")]
+        inside: Option<Inside>,
+    }
+
+    #[test]
+    fn with_adapter_deserializes_off_as_none() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                enable: false
+            "#};
+
+        let o: OutsideOption = serde_yaml::from_str(raw).unwrap();
+
+        assert_eq!(o.inside, None);
+    }
+
+    #[test]
+    fn with_adapter_deserializes_on_as_some() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                enable: true
+                thing: 1
+                other: "Great"
+            "#};
+
+        let o: OutsideOption = serde_yaml::from_str(raw).unwrap();
+
+        assert_eq!(
+            o.inside,
+            Some(Inside {
+                thing: 1,
+                other: "Great".into()
+            })
+        );
+    }
+
+    #[test]
+    fn with_adapter_serializes_some_as_on() {
+        let o = OutsideOption {
+            inside: Some(Inside {
+                thing: 1,
+                other: "Great".into(),
+            }),
+        };
+        let raw = indoc::indoc! {r#"
+            inside:
+              enable: true
+              thing: 1
+              other: Great
+            "#};
+
+        let result = serde_yaml::to_string(&o).unwrap();
+        assert_eq!(result, raw,);
+    }
+
+    #[test]
+    fn with_adapter_serializes_none_as_off() {
+        let o = OutsideOption { inside: None };
+        let raw = indoc::indoc! {r#"
+            inside:
+              enable: false
+            "#};
+
+        let result = serde_yaml::to_string(&o).unwrap();
+        assert_eq!(result, raw,);
+    }
+
+    use crate::EnableKeepingExtras;
+
+    #[derive(Deserialize, Serialize)]
+    struct OutsideKeepingExtras {
+        inside: EnableKeepingExtras<Inside>,
+    }
+
+    #[test]
+    fn disabled_extras_are_preserved() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                enable: false
+                thing: 1
+                other: "Great"
+            "#};
+
+        let o: OutsideKeepingExtras = serde_yaml::from_str(raw).unwrap();
+
+        assert!(!o.inside.is_enabled());
+        let extras = o.inside.disabled_extras().unwrap();
+        assert_eq!(extras["thing"], serde_json::json!(1));
+        assert_eq!(extras["other"], serde_json::json!("Great"));
+    }
+
+    #[test]
+    fn disabled_extras_round_trip_verbatim() {
+        let raw = indoc::indoc! {r#"
+            inside:
+              enable: false
+              thing: 1
+              other: Great
+            "#};
+
+        let o: OutsideKeepingExtras = serde_yaml::from_str(raw).unwrap();
+        let result = serde_yaml::to_string(&o).unwrap();
+
+        assert_eq!(result, raw);
+    }
+
+    #[test]
+    fn enabled_has_no_disabled_extras() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                enable: true
+                thing: 1
+                other: "Great"
+            "#};
+
+        let o: OutsideKeepingExtras = serde_yaml::from_str(raw).unwrap();
+
+        assert!(o.inside.is_enabled());
+        assert!(o.inside.disabled_extras().is_none());
+    }
+
+    use crate::{DisableByDefault, EnableByDefault};
+
+    #[derive(Deserialize, Serialize)]
+    struct OutsideByDefault {
+        inside: EnableByDefault<Inside>,
+    }
+
+    #[test]
+    fn enable_by_default_with_absent_enable_key_is_on() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                thing: 1
+                other: "Great"
+            "#};
+
+        let o: OutsideByDefault = serde_yaml::from_str(raw).unwrap();
+
+        assert!(o.inside.is_enabled());
+        assert_eq!(
+            o.inside.into_inner(),
+            Some(Inside {
+                thing: 1,
+                other: "Great".into()
+            })
+        );
+    }
+
+    #[test]
+    fn enable_by_default_with_explicit_false_is_off() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                enable: false
+            "#};
+
+        let o: OutsideByDefault = serde_yaml::from_str(raw).unwrap();
+
+        assert!(!o.inside.is_enabled());
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct OutsideDisableByDefault {
+        inside: DisableByDefault<Inside>,
+    }
+
+    #[test]
+    fn disable_by_default_with_absent_enable_key_is_off() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                thing: 1
+                other: "Great"
+            "#};
+
+        let o: OutsideDisableByDefault = serde_yaml::from_str(raw).unwrap();
+
+        assert!(!o.inside.is_enabled());
+    }
+
+    #[test]
+    fn disable_by_default_with_explicit_true_is_on() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                enable: true
+                thing: 1
+                other: "Great"
+            "#};
+
+        let o: OutsideDisableByDefault = serde_yaml::from_str(raw).unwrap();
+
+        assert!(o.inside.is_enabled());
+        assert_eq!(
+            o.inside.into_inner(),
+            Some(Inside {
+                thing: 1,
+                other: "Great".into()
+            })
+        );
+    }
+
+    use crate::{EnableTag, TagToken, TaggedEnable};
+
+    struct StatusTag;
+
+    impl EnableTag for StatusTag {
+        const KEY: &'static str = "state";
+        const ON: TagToken = TagToken::Str("enabled");
+        const OFF: TagToken = TagToken::Str("disabled");
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct OutsideTagged {
+        inside: TaggedEnable<Inside, StatusTag>,
+    }
+
+    #[test]
+    fn tagged_enable_deserializes_on_token() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                state: enabled
+                thing: 1
+                other: "Great"
+            "#};
+
+        let o: OutsideTagged = serde_yaml::from_str(raw).unwrap();
+
+        assert!(o.inside.is_enabled());
+        assert_eq!(
+            o.inside.into_inner(),
+            Some(Inside {
+                thing: 1,
+                other: "Great".into()
+            })
+        );
+    }
+
+    #[test]
+    fn tagged_enable_deserializes_off_token() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                state: disabled
+            "#};
+
+        let o: OutsideTagged = serde_yaml::from_str(raw).unwrap();
+
+        assert!(!o.inside.is_enabled());
+    }
+
+    #[test]
+    fn tagged_enable_rejects_unrecognized_token() {
+        let raw = indoc::indoc! {r#"
+            inside:
+                state: other
+            "#};
+
+        let result: Result<OutsideTagged, _> = serde_yaml::from_str(raw);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn tagged_enable_serializes_with_configured_key_and_tokens() {
+        let o = OutsideTagged {
+            inside: TaggedEnable::on(Inside {
+                thing: 1,
+                other: "Great".into(),
+            }),
+        };
+        let raw = indoc::indoc! {r#"
+            inside:
+              other: Great
+              state: enabled
+              thing: 1
+            "#};
+
+        let result = serde_yaml::to_string(&o).unwrap();
+        assert_eq!(result, raw);
+    }
 }